@@ -8,6 +8,7 @@
  */
 
 mod bytes_stream_future;
+mod wire_bytes_decoder;
 
 use std::cmp;
 use std::io;
@@ -25,10 +26,22 @@ use tokio_io::codec::Decoder;
 use tokio_io::AsyncRead;
 
 pub use self::bytes_stream_future::BytesStreamFuture;
+pub use self::wire_bytes_decoder::WireBytesDecoder;
 
 // 8KB is a reasonable default
 const BUFSIZE: usize = 8 * 1024;
 
+/// Replay state for a rewindable [BytesStream] (see [BytesStream::new_rewindable]):
+/// every byte consumed since the last [mark](BytesStream::mark) is retained
+/// here so it can be handed back to the caller by [reset_to_mark](BytesStream::reset_to_mark).
+/// Note the memory tradeoff: this buffer grows without bound until a mark is
+/// dropped, so callers that use `new_rewindable` should call `mark()` as soon
+/// as previously consumed bytes no longer need to be replayable.
+#[derive(Debug, Default)]
+struct Rewind {
+    replay: BytesMut,
+}
+
 /// A structure that wraps a [Stream] of [Bytes] and lets it being accessed both
 /// as a [Stream] and as [AsyncRead]. It is very useful when decoding Stream of
 /// Bytes in an asynchronous way.
@@ -37,6 +50,7 @@ pub struct BytesStream<S> {
     bytes: BytesMut,
     stream: S,
     stream_done: bool,
+    rewind: Option<Rewind>,
 }
 
 impl<S: Stream<Item = Bytes>> BytesStream<S> {
@@ -46,9 +60,53 @@ impl<S: Stream<Item = Bytes>> BytesStream<S> {
             bytes: BytesMut::with_capacity(BUFSIZE),
             stream,
             stream_done: false,
+            rewind: None,
+        }
+    }
+
+    /// Create a new rewindable instance of [BytesStream]. Unlike the default
+    /// constructor, every byte consumed via [Read]/[BufRead] is retained in an
+    /// internal replay buffer instead of being dropped, so it can later be
+    /// replayed with [reset_to_mark](BytesStream::reset_to_mark) -- useful
+    /// when feeding a request body that may need to be resent after a
+    /// mid-stream failure. The replay buffer grows until [mark](BytesStream::mark)
+    /// is called, so callers should mark once previously consumed bytes are
+    /// known not to need replaying.
+    pub fn new_rewindable(stream: S) -> Self {
+        let mut this = Self::new(stream);
+        this.rewind = Some(Rewind::default());
+        this
+    }
+
+    /// Drops all bytes recorded for replay so far, establishing a new replay
+    /// point for a future [reset_to_mark](BytesStream::reset_to_mark). A no-op
+    /// on a `BytesStream` that was not constructed with [new_rewindable](BytesStream::new_rewindable).
+    pub fn mark(&mut self) {
+        if let Some(rewind) = &mut self.rewind {
+            rewind.replay.clear();
         }
     }
 
+    /// Rewinds to the last [mark](BytesStream::mark) (or to the start of the
+    /// stream, if `mark` has never been called), making every byte consumed
+    /// since then available to be read again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `BytesStream` was not constructed with
+    /// [new_rewindable](BytesStream::new_rewindable).
+    pub fn reset_to_mark(&mut self) {
+        let replay = {
+            let rewind = self
+                .rewind
+                .as_mut()
+                .expect("BytesStream::reset_to_mark called on a non-rewindable BytesStream");
+            let len = rewind.replay.len();
+            rewind.replay.split_to(len).freeze()
+        };
+        self.prepend_bytes(replay);
+    }
+
     /// Returns `true` if there are no more bytes left to be consumed
     pub fn is_empty(&self) -> bool {
         self.bytes.is_empty() && self.stream_done
@@ -138,7 +196,10 @@ where
                 len
             };
 
-            self.bytes.split_to(len);
+            let consumed = self.bytes.split_to(len);
+            if let Some(rewind) = &mut self.rewind {
+                rewind.replay.extend_from_slice(&consumed);
+            }
             Ok(len)
         }
     }
@@ -162,7 +223,10 @@ where
     }
 
     fn consume(&mut self, amt: usize) {
-        self.bytes.split_to(amt);
+        let consumed = self.bytes.split_to(amt);
+        if let Some(rewind) = &mut self.rewind {
+            rewind.replay.extend_from_slice(&consumed);
+        }
     }
 }
 
@@ -179,6 +243,11 @@ mod tests {
         BytesStream::new(stream.boxify())
     }
 
+    fn make_rewindable_reader(in_reads: Vec<Vec<u8>>) -> BytesStream<BoxStream<Bytes, io::Error>> {
+        let stream = iter_ok(in_reads.into_iter().map(|v| v.into()));
+        BytesStream::new_rewindable(stream.boxify())
+    }
+
     fn do_read<S>(reader: &mut BytesStream<S>, len_to_read: usize) -> io::Result<Vec<u8>>
     where
         S: Stream<Item = Bytes, Error = io::Error>,
@@ -232,4 +301,39 @@ mod tests {
         assert_eq!(out, vec![]);
         Ok(())
     }
+
+    #[test]
+    fn test_rewindable_reset_to_mark() -> io::Result<()> {
+        let mut reader = make_rewindable_reader(vec![vec![1, 2, 3, 4]]);
+        let out = do_read(&mut reader, 2)?;
+        assert_eq!(out, vec![1, 2]);
+
+        reader.reset_to_mark();
+        let out = do_read(&mut reader, 4)?;
+        assert_eq!(out, vec![1, 2, 3, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rewindable_mark_drops_replay() -> io::Result<()> {
+        let mut reader = make_rewindable_reader(vec![vec![1, 2, 3, 4]]);
+        let out = do_read(&mut reader, 2)?;
+        assert_eq!(out, vec![1, 2]);
+
+        reader.mark();
+        let out = do_read(&mut reader, 2)?;
+        assert_eq!(out, vec![3, 4]);
+
+        reader.reset_to_mark();
+        let out = do_read(&mut reader, 2)?;
+        assert_eq!(out, vec![3, 4]);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "non-rewindable")]
+    fn test_reset_to_mark_panics_on_non_rewindable() {
+        let mut reader = make_reader(vec![vec![1, 2, 3, 4]]);
+        reader.reset_to_mark();
+    }
 }
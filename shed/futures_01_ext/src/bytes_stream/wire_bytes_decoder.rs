@@ -0,0 +1,140 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under both the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree and the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree.
+ */
+
+use std::convert::TryInto;
+use std::io;
+
+use bytes_old::Bytes;
+use bytes_old::BytesMut;
+use tokio_io::codec::Decoder;
+
+const LEN_PREFIX_SIZE: usize = 8;
+
+/// A [Decoder] for the length-prefixed "wire bytes" framing used by
+/// Nix-style daemon/wire protocols: an 8-byte little-endian length `N`,
+/// followed by `N` payload bytes, followed by enough zero padding to round
+/// the frame up to the next multiple of 8 bytes.
+///
+/// Intended to be used with [BytesStream::into_future_decode](super::BytesStream::into_future_decode).
+#[derive(Debug, Clone, Copy)]
+pub struct WireBytesDecoder {
+    max_len: u64,
+}
+
+impl WireBytesDecoder {
+    /// Create a new [WireBytesDecoder]. Decoding fails with an error instead
+    /// of allocating if the frame's declared length exceeds `max_len`, to
+    /// bound the allocation triggered by a hostile or corrupt length prefix.
+    pub fn new(max_len: u64) -> Self {
+        WireBytesDecoder { max_len }
+    }
+}
+
+fn padding_len(len: u64) -> u64 {
+    (8 - len % 8) % 8
+}
+
+impl Decoder for WireBytesDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Bytes>> {
+        if src.len() < LEN_PREFIX_SIZE {
+            return Ok(None);
+        }
+
+        let len = u64::from_le_bytes(src[..LEN_PREFIX_SIZE].try_into().unwrap());
+        if len > self.max_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "wire bytes frame length {} exceeds maximum of {}",
+                    len, self.max_len
+                ),
+            ));
+        }
+
+        let pad = padding_len(len) as usize;
+        let len = len as usize;
+        let frame_len = LEN_PREFIX_SIZE + len + pad;
+        if src.len() < frame_len {
+            src.reserve(frame_len - src.len());
+            return Ok(None);
+        }
+
+        let mut frame = src.split_to(frame_len);
+        frame.split_to(LEN_PREFIX_SIZE);
+        let payload = frame.split_to(len).freeze();
+
+        if frame.iter().any(|&b| b != 0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "non-zero byte in wire bytes frame padding",
+            ));
+        }
+
+        Ok(Some(payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(payload: &[u8]) -> BytesMut {
+        let pad = padding_len(payload.len() as u64) as usize;
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        buf.extend_from_slice(payload);
+        buf.extend_from_slice(&vec![0; pad]);
+        buf
+    }
+
+    #[test]
+    fn test_decode_full_frame() {
+        let mut decoder = WireBytesDecoder::new(1024);
+        let mut buf = frame(b"hello");
+        let item = decoder.decode(&mut buf).unwrap();
+        assert_eq!(item, Some(Bytes::from_static(b"hello")));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_decode_aligned_frame() {
+        let mut decoder = WireBytesDecoder::new(1024);
+        let mut buf = frame(b"12345678");
+        let item = decoder.decode(&mut buf).unwrap();
+        assert_eq!(item, Some(Bytes::from_static(b"12345678")));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_decode_incomplete_frame() {
+        let mut decoder = WireBytesDecoder::new(1024);
+        let mut buf = frame(b"hello");
+        buf.truncate(buf.len() - 1);
+        assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_rejects_oversized_length() {
+        let mut decoder = WireBytesDecoder::new(4);
+        let mut buf = frame(b"hello");
+        assert!(decoder.decode(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_non_zero_padding() {
+        let mut decoder = WireBytesDecoder::new(1024);
+        let mut buf = frame(b"hello");
+        let last = buf.len() - 1;
+        buf[last] = 1;
+        assert!(decoder.decode(&mut buf).is_err());
+    }
+}
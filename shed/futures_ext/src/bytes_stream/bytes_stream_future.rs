@@ -0,0 +1,76 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under both the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree and the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree.
+ */
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use futures::Stream;
+use tokio_util::codec::Decoder;
+
+use super::BytesStream;
+
+/// Future that resolves to a single item decoded out of a [BytesStream],
+/// together with the remaining [BytesStream], as returned by
+/// [BytesStream::into_future_decode].
+pub struct BytesStreamFuture<S, Dec> {
+    state: Option<(BytesStream<S>, Dec)>,
+}
+
+impl<S, Dec> BytesStreamFuture<S, Dec> {
+    pub(super) fn new(bytes_stream: BytesStream<S>, decoder: Dec) -> Self {
+        BytesStreamFuture {
+            state: Some((bytes_stream, decoder)),
+        }
+    }
+}
+
+impl<S, Dec> Future for BytesStreamFuture<S, Dec>
+where
+    S: Stream<Item = io::Result<Bytes>> + Unpin,
+    Dec: Decoder + Unpin,
+    Dec::Error: From<io::Error>,
+{
+    type Output = Result<(Option<Dec::Item>, BytesStream<S>), Dec::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let (mut bytes_stream, mut decoder) = self
+            .state
+            .take()
+            .expect("BytesStreamFuture polled after completion");
+
+        loop {
+            match decoder.decode(&mut bytes_stream.bytes) {
+                Ok(Some(item)) => return Poll::Ready(Ok((Some(item), bytes_stream))),
+                Ok(None) => {}
+                Err(e) => return Poll::Ready(Err(e.into())),
+            }
+
+            if bytes_stream.stream_done {
+                let item = match decoder.decode_eof(&mut bytes_stream.bytes) {
+                    Ok(item) => item,
+                    Err(e) => return Poll::Ready(Err(e.into())),
+                };
+                return Poll::Ready(Ok((item, bytes_stream)));
+            }
+
+            match Pin::new(&mut bytes_stream).poll_buffer(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+                Poll::Pending => {
+                    self.state = Some((bytes_stream, decoder));
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under both the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree and the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree.
+ */
+
+mod bytes_stream_future;
+mod reader_stream;
+
+use std::cmp;
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+use futures::ready;
+use futures::Stream;
+use pin_project::pin_project;
+use tokio::io::AsyncBufRead;
+use tokio::io::AsyncRead;
+use tokio::io::ReadBuf;
+use tokio_util::codec::Decoder;
+
+pub use self::bytes_stream_future::BytesStreamFuture;
+pub use self::reader_stream::ReaderStream;
+
+// 8KB is a reasonable default
+const BUFSIZE: usize = 8 * 1024;
+
+/// A structure that wraps a [Stream] of [io::Result<Bytes>] and lets its bytes
+/// be read through [AsyncRead]/[AsyncBufRead]. It is the
+/// futures 0.3 / tokio 1.x counterpart of `futures_01_ext`'s `BytesStream`,
+/// for callers that don't want to depend on the legacy `bytes_old`/`tokio-io`
+/// stack.
+#[pin_project]
+#[derive(Debug)]
+pub struct BytesStream<S> {
+    bytes: BytesMut,
+    #[pin]
+    stream: S,
+    stream_done: bool,
+}
+
+impl<S: Stream<Item = io::Result<Bytes>>> BytesStream<S> {
+    /// Create a new instance of [BytesStream] wrapping the given [Stream] of
+    /// [io::Result<Bytes>]
+    pub fn new(stream: S) -> Self {
+        BytesStream {
+            bytes: BytesMut::with_capacity(BUFSIZE),
+            stream,
+            stream_done: false,
+        }
+    }
+
+    /// Returns `true` if there are no more bytes left to be consumed
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty() && self.stream_done
+    }
+
+    /// Consumes this combinator returning a pair of bytes that have been received,
+    /// but not yet consumed and the Stream that can possibly yield more bytes
+    pub fn into_parts(self) -> (Bytes, S) {
+        (self.bytes.freeze(), self.stream)
+    }
+
+    /// Returns a future that yields a single decoded item from the Bytes of this
+    /// BytesStream (if any) and the remaining BytesStream.
+    pub fn into_future_decode<Dec>(self, decoder: Dec) -> BytesStreamFuture<S, Dec>
+    where
+        Dec: Decoder,
+        Dec::Error: From<io::Error>,
+    {
+        BytesStreamFuture::new(self, decoder)
+    }
+
+    /// Adds some bytes to the front of the BytesStream internal buffer. Those
+    /// bytes are ready to be read immediately after this function completes.
+    pub fn prepend_bytes(&mut self, bytes: Bytes) {
+        let cap = cmp::max(BUFSIZE, bytes.len() + self.bytes.len());
+        let mut bytes_mut = BytesMut::with_capacity(cap);
+        bytes_mut.extend_from_slice(&bytes);
+        bytes_mut.extend_from_slice(&self.bytes);
+        self.bytes = bytes_mut;
+    }
+
+    fn poll_buffer(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        if !*this.stream_done {
+            match ready!(this.stream.poll_next(cx)) {
+                None => *this.stream_done = true,
+                Some(Ok(bytes)) => this.bytes.extend_from_slice(&bytes),
+                Some(Err(e)) => return Poll::Ready(Err(e)),
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_buffer_until(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        len: usize,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            let this = self.as_mut().project();
+            if *this.stream_done || this.bytes.len() >= len {
+                return Poll::Ready(Ok(()));
+            }
+            ready!(self.as_mut().poll_buffer(cx))?;
+        }
+    }
+}
+
+impl<S: Stream<Item = io::Result<Bytes>>> From<S> for BytesStream<S> {
+    fn from(stream: S) -> Self {
+        BytesStream::new(stream)
+    }
+}
+
+impl<S> AsyncRead for BytesStream<S>
+where
+    S: Stream<Item = io::Result<Bytes>>,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        ready!(self.as_mut().poll_buffer_until(cx, buf.remaining()))?;
+
+        let this = self.project();
+        let len = cmp::min(buf.remaining(), this.bytes.len());
+        if len > 0 {
+            buf.put_slice(&this.bytes[..len]);
+            this.bytes.advance(len);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S> AsyncBufRead for BytesStream<S>
+where
+    S: Stream<Item = io::Result<Bytes>>,
+{
+    fn poll_fill_buf(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        ready!(self.as_mut().poll_buffer_until(cx, 1))?;
+        let this = self.project();
+        Poll::Ready(Ok(&this.bytes[..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.project();
+        this.bytes.advance(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use futures::StreamExt;
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    fn make_reader(
+        in_reads: Vec<Vec<u8>>,
+    ) -> BytesStream<impl Stream<Item = io::Result<Bytes>>> {
+        let stream = stream::iter(in_reads.into_iter().map(|v| Ok(Bytes::from(v))));
+        BytesStream::new(stream)
+    }
+
+    #[tokio::test]
+    async fn test_read_once() -> io::Result<()> {
+        let mut reader = make_reader(vec![vec![1, 2, 3, 4]]);
+        let mut out = vec![0; 4];
+        reader.read_exact(&mut out).await?;
+        assert_eq!(out, vec![1, 2, 3, 4]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_join() -> io::Result<()> {
+        let mut reader = make_reader(vec![vec![1, 2], vec![3, 4]]);
+        let mut out = vec![0; 4];
+        reader.read_exact(&mut out).await?;
+        assert_eq!(out, vec![1, 2, 3, 4]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_eof() -> io::Result<()> {
+        let mut reader = make_reader(vec![vec![1, 2, 3]]);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).await?;
+        assert_eq!(out, vec![1, 2, 3]);
+        assert!(reader.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under both the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree and the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use bytes::BytesMut;
+use futures::ready;
+use futures::Stream;
+use pin_project::pin_project;
+use tokio::io::AsyncRead;
+use tokio::io::ReadBuf;
+
+/// The inverse of [BytesStream](super::BytesStream): wraps an [AsyncRead] and
+/// exposes it as a [Stream] of [io::Result<Bytes>] chunks, each up to
+/// `capacity` bytes, until the reader reaches EOF.
+#[pin_project]
+pub struct ReaderStream<R> {
+    #[pin]
+    reader: R,
+    capacity: usize,
+    done: bool,
+}
+
+impl<R: AsyncRead> ReaderStream<R> {
+    /// Create a new [ReaderStream] wrapping `reader`, reading in chunks of at
+    /// most `capacity` bytes.
+    pub fn new(reader: R, capacity: usize) -> Self {
+        ReaderStream {
+            reader,
+            capacity,
+            done: false,
+        }
+    }
+}
+
+impl<R: AsyncRead> Stream for ReaderStream<R> {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let mut chunk = BytesMut::with_capacity(*this.capacity);
+        let mut buf = ReadBuf::uninit(chunk.spare_capacity_mut());
+        match ready!(this.reader.poll_read(cx, &mut buf)) {
+            Ok(()) => {
+                let filled = buf.filled().len();
+                if filled == 0 {
+                    *this.done = true;
+                    return Poll::Ready(None);
+                }
+                // Safety: `poll_read` only returns `Ok` after initializing the
+                // first `filled` bytes of `buf`, which alias `chunk`'s spare
+                // capacity.
+                unsafe { chunk.set_len(filled) };
+                Poll::Ready(Some(Ok(chunk.freeze())))
+            }
+            Err(e) => {
+                *this.done = true;
+                Poll::Ready(Some(Err(e)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reader_stream_chunks() {
+        let data = vec![1, 2, 3, 4, 5];
+        let mut stream = ReaderStream::new(data.as_slice(), 2);
+
+        let mut out = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            out.push(chunk.unwrap());
+        }
+
+        assert_eq!(out, vec![Bytes::from(vec![1, 2]), Bytes::from(vec![3, 4]), Bytes::from(vec![5])]);
+    }
+
+    #[tokio::test]
+    async fn test_reader_stream_empty() {
+        let data: &[u8] = &[];
+        let mut stream = ReaderStream::new(data, 8 * 1024);
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -0,0 +1,18 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under both the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree and the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree.
+ */
+
+//! Extensions for the futures 0.3 / tokio 1.x async ecosystem. This is the
+//! futures 0.3 counterpart of `futures_01_ext`, for crates that don't want to
+//! depend on the legacy `futures` 0.1 / `tokio-io` stack.
+
+mod bytes_stream;
+
+pub use crate::bytes_stream::BytesStream;
+pub use crate::bytes_stream::BytesStreamFuture;
+pub use crate::bytes_stream::ReaderStream;